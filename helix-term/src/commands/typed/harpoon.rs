@@ -1,7 +1,45 @@
 use serde::{Deserialize, Serialize};
 
+use helix_core::Rope;
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
 use super::*;
 
+/// Monotonic counter making each store temp file name unique within a process;
+/// combined with the pid it keeps concurrent writers off a shared temp path.
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// The live filesystem watcher, kept for the session so repeated `:harpoon
+/// watch` calls reconcile the watched set in place instead of leaking a thread.
+static WATCH_STATE: Mutex<Option<WatchState>> = Mutex::new(None);
+
+/// Dispatcher for a `:harpoon <subcommand>` command: routes the leading
+/// subcommand to its handler, covering every subcommand — including `pick`,
+/// `prune` and `watch`.
+pub fn harpoon(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    let (sub, rest) = args
+        .split_first()
+        .ok_or_else(|| anyhow!("expected a harpoon subcommand"))?;
+
+    match sub.as_ref() {
+        "set" => set(cx, rest, event),
+        "get" => get(cx, rest, event),
+        "remove" => remove(cx, rest, event),
+        "update" => update(cx, rest, event),
+        "list" => list(cx, rest, event),
+        "pick" => pick(cx, rest, event),
+        "prune" => prune(cx, rest, event),
+        "watch" => watch(cx, rest, event),
+        other => Err(anyhow!("unknown harpoon subcommand '{}'", other)),
+    }
+}
+
 pub fn set(
     cx: &mut compositor::Context,
     args: &[Cow<str>],
@@ -21,7 +59,7 @@ pub fn set(
     let selection = doc.selection(view.id);
 
     let mut store = Store::open()?;
-    store.set_file(index, File::new(path.clone(), selection));
+    store.set_file(index, File::new(path.clone(), selection, doc.text()));
     store.save()?;
 
     let path_str = path.to_string_lossy().to_string();
@@ -47,10 +85,82 @@ pub fn get(
         return Ok(());
     };
 
-    let _ = cx.editor.open(&file.path, Action::Replace)?;
-    let (view, doc) = current!(cx.editor);
-    doc.set_selection(view.id, file.as_selection());
+    open_file(cx.editor, None, file)?;
+
+    Ok(())
+}
+
+/// Opens `file`, restores its stored selection and centers the view on it.
+/// The stored path is resolved against `root` when given (a mark from another
+/// project), otherwise against the current working directory.
+///
+/// Shared by `get` and the interactive picker so both reach a mark the same way.
+fn open_file(editor: &mut Editor, root: Option<&Path>, file: &File) -> anyhow::Result<()> {
+    let path = root.map_or_else(|| file.path.to_path_buf(), |root| root.join(&file.path));
+    editor.open(&path, Action::Replace)?;
+    let (view, doc) = current!(editor);
+    let selection = file.as_selection(doc.text());
+    doc.set_selection(view.id, selection);
     align_view(doc, view, Align::Center);
+    Ok(())
+}
+
+pub fn pick(
+    cx: &mut compositor::Context,
+    args: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let all = args.iter().any(|arg| arg.as_ref() == "--all");
+
+    let mut store = Store::open()?;
+    let mut entries = Vec::new();
+    if all {
+        for (root, project) in std::mem::take(&mut store.projects) {
+            for (index, file) in project.files {
+                entries.push(Entry {
+                    root: Some(root.clone()),
+                    index,
+                    file,
+                });
+            }
+        }
+    } else {
+        let project = store.project();
+        for (index, file) in std::mem::take(&mut project.files) {
+            entries.push(Entry {
+                root: None,
+                index,
+                file,
+            });
+        }
+    }
+    entries.sort_unstable_by(|a, b| (&a.root, a.index).cmp(&(&b.root, b.index)));
+
+    let columns = [
+        ui::PickerColumn::new("#", |entry: &Entry, _| entry.index.to_string().into()),
+        ui::PickerColumn::new("path", |entry: &Entry, _| entry.display().into()),
+    ];
+
+    let picker = ui::Picker::new(columns, 1, entries, (), |cx, entry, _action| {
+        if let Err(err) = open_file(cx.editor, entry.root.as_deref(), &entry.file) {
+            cx.editor.set_error(err.to_string());
+        }
+    });
+
+    let callback = async move {
+        let call: job::Callback = Callback::EditorCompositor(Box::new(
+            move |_editor: &mut Editor, compositor: &mut Compositor| {
+                compositor.push(Box::new(ui::overlay::overlaid(picker)));
+            },
+        ));
+        Ok(call)
+    };
+
+    cx.jobs.callback(callback);
 
     Ok(())
 }
@@ -67,17 +177,20 @@ pub fn remove(
     let index = index(args.first())?;
 
     let mut store = Store::open()?;
-    let file = store.remove_file(index);
-    if let Some(file) = file {
-        cx.editor.set_status(format!(
-            "'{}' removed from #{}",
-            file.path.to_string_lossy(),
-            index
-        ));
-        store.save()?;
-    } else {
-        cx.editor
-            .set_error(format!("No file assigned to #{}", index));
+    let path = store
+        .project()
+        .files
+        .get(&index)
+        .map(|file| file.path.to_string_lossy().into_owned());
+    match path {
+        Some(path) => {
+            cx.editor
+                .set_status(format!("'{}' removed from #{}", path, index));
+            store.remove_index(index)?;
+        }
+        None => cx
+            .editor
+            .set_error(format!("No file assigned to #{}", index)),
     }
 
     Ok(())
@@ -102,7 +215,7 @@ pub fn update(
     let project = store.project();
     if let Some(file) = project.files.values_mut().find(|file| file.path == path) {
         let selection = doc.selection(view.id);
-        file.update_selection(selection);
+        file.update_selection(selection, doc.text());
     }
     store.save()?;
 
@@ -145,12 +258,96 @@ pub fn list(
     Ok(())
 }
 
+pub fn prune(
+    cx: &mut compositor::Context,
+    _: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    let mut store = Store::open()?;
+    // Fold in concurrent on-disk changes before collecting so GC doesn't drop
+    // marks another instance added since we opened.
+    if let Ok(on_disk) = Store::open() {
+        store.merge(on_disk);
+    }
+    let reclaimed = store.prune();
+    store.overwrite()?;
+
+    cx.editor
+        .set_status(format!("pruned {} dead harpoon entr{}", reclaimed, {
+            if reclaimed == 1 {
+                "y"
+            } else {
+                "ies"
+            }
+        }));
+
+    Ok(())
+}
+
+pub fn watch(
+    cx: &mut compositor::Context,
+    _: &[Cow<str>],
+    event: PromptEvent,
+) -> anyhow::Result<()> {
+    if event != PromptEvent::Validate {
+        return Ok(());
+    }
+
+    match Store::watch() {
+        Ok(true) => cx.editor.set_status("watching harpooned files for moves"),
+        // The platform backend could not be initialised; marks keep working,
+        // they just won't follow external renames.
+        Ok(false) => cx
+            .editor
+            .set_error("filesystem watcher unavailable on this platform"),
+        Err(err) => cx.editor.set_error(err.to_string()),
+    }
+
+    Ok(())
+}
+
+/// A single picker row: a harpoon [`File`] plus the index it sits at and, when
+/// browsing across projects, the project root it belongs to.
+struct Entry {
+    root: Option<PathBuf>,
+    index: usize,
+    file: File<'static>,
+}
+
+impl Entry {
+    /// The matchable path string, prefixed with the project root when browsing
+    /// across projects so cross-repo marks stay distinguishable.
+    fn display(&self) -> String {
+        match &self.root {
+            Some(root) => format!(
+                "{}{}{}",
+                root.to_string_lossy(),
+                std::path::MAIN_SEPARATOR,
+                self.file.path.to_string_lossy()
+            ),
+            None => self.file.path.to_string_lossy().into_owned(),
+        }
+    }
+}
+
 #[derive(Default, Serialize, Deserialize)]
 struct Store<'a> {
+    /// Schema version of the on-disk store. Absent in pre-anchoring stores,
+    /// which deserialize as `0` and fall back to raw offset remapping.
+    #[serde(default)]
+    version: u32,
     projects: HashMap<PathBuf, Project<'a>>,
 }
 
 impl<'a> Store<'a> {
+    /// Current on-disk schema version. Bumped when [`Span`] gained line/column
+    /// anchoring alongside the raw offsets.
+    const VERSION: u32 = 1;
+
     fn open() -> anyhow::Result<Self> {
         match std::fs::read_to_string(helix_loader::harpoon_store_file()) {
             Ok(v) => Ok(serde_json::from_str(&v)?),
@@ -159,32 +356,250 @@ impl<'a> Store<'a> {
         }
     }
 
-    fn save(self) -> anyhow::Result<()> {
-        let store = serde_json::to_string(&self)?;
-        std::fs::write(helix_loader::harpoon_store_file(), store)?;
+    fn save(mut self) -> anyhow::Result<()> {
+        // Fold in whatever another instance has written since we opened, so two
+        // editors sharing the store can't clobber each other's marks.
+        if let Ok(on_disk) = Store::open() {
+            self.merge(on_disk);
+        }
+        self.overwrite()
+    }
+
+    /// Writes the in-memory store to disk verbatim, without merging on-disk
+    /// state. Used by destructive rewrites like [`Store::prune`], which must be
+    /// authoritative rather than union dead entries back in.
+    fn overwrite(&mut self) -> anyhow::Result<()> {
+        self.version = Self::VERSION;
+
+        let path = helix_loader::harpoon_store_file();
+        let contents = serde_json::to_string(self)?;
+
+        // Write through a sibling temp file and rename it into place so a crash
+        // mid-write can never leave a truncated, unparseable store behind. The
+        // temp name carries the pid and a per-process counter so two instances
+        // saving at once never share — and thus never interleave into — one temp.
+        let counter = TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let tmp = path.with_extension(format!("{}.{}.tmp", std::process::id(), counter));
+        std::fs::write(&tmp, contents)?;
+        std::fs::rename(&tmp, &path)?;
+
         Ok(())
     }
 
+    /// Unions `other` into `self` at the [`Project::files`] level: projects and
+    /// indices only present on disk are preserved, while indices present in both
+    /// keep this (in-memory) instance's value — last writer wins.
+    fn merge(&mut self, other: Store<'a>) {
+        for (root, project) in other.projects {
+            let ours = self.projects.entry(root).or_default();
+            for (index, file) in project.files {
+                ours.files.entry(index).or_insert(file);
+            }
+        }
+    }
+
     fn set_file(&mut self, index: usize, file: File<'a>) {
         let project = self.project();
         project.files.insert(index, file);
     }
 
-    fn remove_file(&mut self, index: usize) -> Option<File> {
-        let project = self.project();
-        project.files.remove(&index)
+    /// Merge-aware deletion: folds in whatever another instance has written
+    /// since we opened, then drops `index` from the current project before
+    /// writing. This neither resurrects the removed mark (unlike a plain
+    /// [`save`](Self::save)) nor clobbers projects and indices the other
+    /// instance added (unlike a wholesale [`overwrite`](Self::overwrite)).
+    fn remove_index(mut self, index: usize) -> anyhow::Result<()> {
+        if let Ok(on_disk) = Store::open() {
+            self.merge(on_disk);
+        }
+        self.project().files.remove(&index);
+        self.overwrite()
     }
 
     fn file(&mut self, index: usize) -> Option<&File> {
-        let project = self.project();
+        let root = helix_stdx::env::current_working_dir();
+        let project = self.projects.entry(root.clone()).or_default();
         let file = project.files.get(&index);
-        file.filter(|file| file.path.exists())
+        // Resolve against the project root, matching `prune`'s existence check so
+        // garbage collection and read-time filtering agree on which marks are dead.
+        file.filter(|file| root.join(&file.path).exists())
     }
 
     fn project(&mut self) -> &mut Project<'a> {
         let cwd = helix_stdx::env::current_working_dir();
         self.projects.entry(cwd.clone()).or_default()
     }
+
+    /// Drops dead entries from the store: [`File`]s whose `path` no longer
+    /// exists, then [`Project`]s whose root key is gone or whose `files` map is
+    /// left empty. Returns the number of files and projects reclaimed.
+    fn prune(&mut self) -> usize {
+        let mut reclaimed = 0;
+
+        self.projects.retain(|root, project| {
+            project.files.retain(|_, file| {
+                let exists = root.join(&file.path).exists();
+                if !exists {
+                    reclaimed += 1;
+                }
+                exists
+            });
+
+            let keep = root.exists() && !project.files.is_empty();
+            if !keep {
+                reclaimed += 1;
+            }
+            keep
+        });
+
+        reclaimed
+    }
+
+    /// Spawns a background watcher over the directories holding the current
+    /// project's harpooned files and rewrites a mark's `path` whenever its file
+    /// is renamed or moved on disk.
+    ///
+    /// A move that notify reports as a single paired event is handled directly;
+    /// a move split into separate `From`/`To` events (the common case on the
+    /// inotify backend) is correlated by its rename cookie. Moving a file to a
+    /// directory that holds no other marks stays outside the watched set, so
+    /// such a destination can't be observed.
+    ///
+    /// A single watcher lives for the session: the first call spawns it, and
+    /// every later call reconciles the watched directories against the *current*
+    /// marks — adding directories for newly harpooned files and dropping ones no
+    /// longer referenced — rather than leaking a thread or freezing the initial
+    /// snapshot.
+    ///
+    /// Returns `Ok(false)` when the platform has no usable watcher backend, in
+    /// which case marks keep working but simply don't follow external moves.
+    fn watch() -> anyhow::Result<bool> {
+        use notify::{RecursiveMode, Watcher};
+
+        let cwd = helix_stdx::env::current_working_dir();
+        let mut store = Store::open()?;
+        let project = store.project();
+
+        // The unique directories we need to observe, resolved against the cwd
+        // since stored paths are project-relative.
+        let mut dirs = project
+            .files
+            .values()
+            .filter_map(|file| cwd.join(&file.path).parent().map(Path::to_path_buf))
+            .collect::<Vec<_>>();
+        dirs.sort_unstable();
+        dirs.dedup();
+
+        let mut guard = WATCH_STATE.lock().unwrap();
+        if guard.is_none() {
+            let (tx, rx) = std::sync::mpsc::channel();
+            let watcher = match notify::recommended_watcher(move |res| {
+                let _ = tx.send(res);
+            }) {
+                Ok(watcher) => watcher,
+                Err(_) => return Ok(false),
+            };
+            Self::spawn_watcher_thread(cwd.clone(), rx);
+            *guard = Some(WatchState {
+                watcher,
+                dirs: Vec::new(),
+            });
+        }
+
+        let state = guard.as_mut().expect("watcher state just initialised");
+
+        // Reconcile: stop watching directories that no longer hold a mark, and
+        // start watching any that appeared since the last call.
+        for dir in &state.dirs {
+            if !dirs.contains(dir) {
+                let _ = state.watcher.unwatch(dir);
+            }
+        }
+        for dir in &dirs {
+            if !state.dirs.contains(dir) {
+                let _ = state.watcher.watch(dir, RecursiveMode::NonRecursive);
+            }
+        }
+        state.dirs = dirs;
+
+        Ok(true)
+    }
+
+    /// Consumes watcher events for the lifetime of the process, rewriting a
+    /// mark's `path` whenever its file is renamed or moved on disk.
+    fn spawn_watcher_thread(
+        cwd: PathBuf,
+        rx: std::sync::mpsc::Receiver<notify::Result<notify::Event>>,
+    ) {
+        use notify::{
+            event::{ModifyKind, RenameMode},
+            EventKind,
+        };
+
+        std::thread::spawn(move || {
+            // `From` events awaiting their matching `To`, keyed by rename cookie.
+            let mut pending: HashMap<usize, PathBuf> = HashMap::new();
+            for event in rx.into_iter().flatten() {
+                match event.kind {
+                    EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
+                        let [from, to] = event.paths.as_slice() else {
+                            continue;
+                        };
+                        let _ = Self::relocate(&cwd, from, to);
+                    }
+                    // A split rename: stash the source path and complete it when
+                    // the sibling `To` event arrives under the same cookie.
+                    EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+                        if let (Some(cookie), Some(from)) =
+                            (event.attrs.tracker(), event.paths.first())
+                        {
+                            pending.insert(cookie, from.clone());
+                        }
+                    }
+                    EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+                        if let (Some(cookie), Some(to)) =
+                            (event.attrs.tracker(), event.paths.first())
+                        {
+                            if let Some(from) = pending.remove(&cookie) {
+                                let _ = Self::relocate(&cwd, &from, to);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        });
+    }
+
+    /// Points the mark at `from` (if any) to `to` and persists the change.
+    fn relocate(cwd: &Path, from: &Path, to: &Path) -> anyhow::Result<()> {
+        let from = path::get_relative_path(from);
+        let to = path::get_relative_path(to);
+
+        let mut store = Store::open()?;
+        let project = store.projects.entry(cwd.to_path_buf()).or_default();
+        let mut moved = false;
+        for file in project.files.values_mut() {
+            if file.path == from {
+                file.path = to.clone();
+                moved = true;
+            }
+        }
+
+        if moved {
+            store.save()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The session's live filesystem watcher together with the directories it is
+/// currently observing, so successive `:harpoon watch` calls can reconcile the
+/// set in place.
+struct WatchState {
+    watcher: notify::RecommendedWatcher,
+    dirs: Vec<PathBuf>,
 }
 
 #[derive(Default, Serialize, Deserialize)]
@@ -199,37 +614,28 @@ struct File<'a> {
 }
 
 impl<'a> File<'a> {
-    fn new(path: Cow<'a, Path>, selection: &Selection) -> Self {
+    fn new(path: Cow<'a, Path>, selection: &Selection, text: &Rope) -> Self {
         Self {
             path: path.clone(),
             spans: selection
                 .ranges()
                 .iter()
-                .map(|range| Span {
-                    start: range.anchor,
-                    end: range.head,
-                })
+                .map(|range| Span::capture(range, text))
                 .collect(),
         }
     }
 
-    fn update_selection(&mut self, selection: &Selection) {
+    fn update_selection(&mut self, selection: &Selection, text: &Rope) {
         self.spans = selection
             .ranges()
             .iter()
-            .map(|range| Span {
-                start: range.anchor,
-                end: range.head,
-            })
+            .map(|range| Span::capture(range, text))
             .collect()
     }
 
-    fn as_selection(&self) -> Selection {
+    fn as_selection(&self, text: &Rope) -> Selection {
         Selection::new(
-            self.spans
-                .iter()
-                .map(|span| Range::new(span.start, span.end))
-                .collect(),
+            self.spans.iter().map(|span| span.resolve(text)).collect(),
             0,
         )
     }
@@ -239,6 +645,113 @@ impl<'a> File<'a> {
 struct Span {
     start: usize,
     end: usize,
+    /// Line/column anchors for the `start` and `end` endpoints, used to remap
+    /// the offsets after the file was edited outside the editor. Absent in
+    /// pre-anchoring stores, in which case the raw offsets are used as-is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    anchors: Option<(Anchor, Anchor)>,
+}
+
+impl Span {
+    fn capture(range: &Range, text: &Rope) -> Self {
+        Self {
+            start: range.anchor,
+            end: range.head,
+            anchors: Some((Anchor::capture(text, range.anchor), Anchor::capture(text, range.head))),
+        }
+    }
+
+    /// Rebuilds the range against `text`.
+    ///
+    /// Legacy entries without anchors fall back to the stored offsets. When
+    /// anchors are present, each endpoint resolves independently: if its line is
+    /// still intact (same index and content fingerprint) it remaps exactly to
+    /// the line's current char offset; otherwise it degrades to a clamped nearby
+    /// position. As a fast path, when both exact remaps reproduce the captured
+    /// offsets — i.e. the document is unchanged — the raw offsets are returned
+    /// directly.
+    fn resolve(&self, text: &Rope) -> Range {
+        let Some((start, end)) = &self.anchors else {
+            return Range::new(self.start, self.end);
+        };
+
+        match (start.exact(text), end.exact(text)) {
+            (Some(s), Some(e)) if s == self.start && e == self.end => {
+                Range::new(self.start, self.end)
+            }
+            (Some(s), Some(e)) => Range::new(s, e),
+            (s, e) => Range::new(
+                s.unwrap_or_else(|| start.clamp(text)),
+                e.unwrap_or_else(|| end.clamp(text)),
+            ),
+        }
+    }
+}
+
+/// A (line, column) endpoint plus a fingerprint of that line's text, letting a
+/// stored offset survive external edits: if the line still hashes the same we
+/// remap exactly, otherwise we clamp to a sane nearby position.
+#[derive(Serialize, Deserialize)]
+struct Anchor {
+    line: usize,
+    column: usize,
+    fingerprint: u64,
+}
+
+impl Anchor {
+    fn capture(text: &Rope, offset: usize) -> Self {
+        let line = text.char_to_line(offset);
+        let column = offset - text.line_to_char(line);
+        Self {
+            line,
+            column,
+            fingerprint: fingerprint(text, line),
+        }
+    }
+
+    /// The exact remapped offset when the anchored line is intact — same index
+    /// and matching content fingerprint — or `None` once the line moved or its
+    /// text changed. A match means the line survived external edits, so the only
+    /// thing that can have shifted is its starting char offset.
+    fn exact(&self, text: &Rope) -> Option<usize> {
+        (self.line < text.len_lines() && fingerprint(text, self.line) == self.fingerprint)
+            .then(|| text.line_to_char(self.line) + self.column.min(line_len(text, self.line)))
+    }
+
+    /// The nearest sane offset when the anchor no longer matches: clamp the line
+    /// index into range and the column to that line's length, so the mark
+    /// degrades to a position near where it was rather than failing.
+    fn clamp(&self, text: &Rope) -> usize {
+        let line = self.line.min(text.len_lines().saturating_sub(1));
+        text.line_to_char(line) + self.column.min(line_len(text, line))
+    }
+}
+
+/// Number of characters on `line`, excluding its trailing line break.
+fn line_len(text: &Rope, line: usize) -> usize {
+    let slice = text.line(line);
+    let mut len = slice.len_chars();
+    if len > 0 && slice.char(len - 1) == '\n' {
+        len -= 1;
+        if len > 0 && slice.char(len - 1) == '\r' {
+            len -= 1;
+        }
+    }
+    len
+}
+
+/// A cheap content fingerprint of a single line, used to tell whether a stored
+/// anchor still points at the same text.
+fn fingerprint(text: &Rope, line: usize) -> u64 {
+    use std::hash::Hasher;
+
+    // Feed raw bytes so the hash depends only on the line's text, not on how
+    // the rope happens to split it into chunks.
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    for chunk in text.line(line).chunks() {
+        hasher.write(chunk.as_bytes());
+    }
+    hasher.finish()
 }
 
 fn index(arg: Option<&Cow<str>>) -> anyhow::Result<usize> {